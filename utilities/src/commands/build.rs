@@ -2,53 +2,84 @@ use std::fs;
 use std::path::Path;
 use std::process::{self, Command};
 
-pub fn run(output_name: Option<&str>) {
+use crate::manifest::{Manifest, Profile};
+
+pub fn run(output_name: Option<&str>, target_arg: Option<&str>, profile_arg: Option<&str>, manifest: &Manifest) {
     let name = output_name.unwrap_or("game");
     let build_dir = Path::new("build");
 
-    println!("Building game (without editor)...");
+    if let Err(e) = fs::create_dir_all(build_dir) {
+        eprintln!("Error creating build directory: {e}");
+        process::exit(1);
+    }
+
+    let profile = profile_arg.map(|p| match manifest.profiles.get(p) {
+        Some(profile) => profile.clone(),
+        None => {
+            eprintln!("Error: no such profile \"{p}\" in {}", crate::manifest::MANIFEST_FILE);
+            process::exit(1);
+        }
+    });
 
-    // Create build directory
-    if !build_dir.exists() {
-        if let Err(e) = fs::create_dir_all(build_dir) {
-            eprintln!("Error creating build directory: {e}");
+    let targets: Vec<String> = if let Some(t) = target_arg {
+        t.split(',').map(|s| s.trim().to_string()).collect()
+    } else if let Some(profile) = &profile {
+        if profile.targets.is_empty() {
+            eprintln!("Error: profile \"{}\" has no targets configured", profile_arg.unwrap());
             process::exit(1);
         }
+        profile.targets.clone()
+    } else {
+        vec![host_target().to_string()]
+    };
+
+    for target in &targets {
+        println!("Building game for {target} (without editor)...");
+        build_for_target(target, name, build_dir, manifest, profile.as_ref());
     }
+}
 
-    // On macOS, create an .app bundle
-    #[cfg(target_os = "macos")]
-    {
-        build_macos_app(name, build_dir);
+fn host_target() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "macos"
+    } else if cfg!(target_os = "windows") {
+        "windows"
+    } else {
+        "linux"
     }
+}
 
-    // Non-macOS: just build the binary
-    #[cfg(not(target_os = "macos"))]
-    {
-        build_binary(name, build_dir);
+fn build_for_target(target: &str, name: &str, build_dir: &Path, manifest: &Manifest, profile: Option<&Profile>) {
+    match target {
+        "macos" => build_macos_app(name, build_dir, manifest, profile),
+        "windows" => build_windows(name, build_dir, manifest, profile),
+        "linux" => build_linux_appdir(name, build_dir, manifest, profile),
+        "wasm" => build_wasm(name, build_dir, manifest),
+        other => {
+            eprintln!("Error: unknown build target \"{other}\" (expected windows, linux, macos, or wasm)");
+            process::exit(1);
+        }
     }
 }
 
-#[cfg(target_os = "macos")]
-fn build_macos_app(name: &str, build_dir: &Path) {
-    let app_name = format!("{}.app", name);
+fn build_macos_app(name: &str, build_dir: &Path, manifest: &Manifest, profile: Option<&Profile>) {
+    let app_name = format!("{name}.app");
     let app_path = build_dir.join(&app_name);
     let contents_path = app_path.join("Contents");
     let macos_path = contents_path.join("MacOS");
     let resources_path = contents_path.join("Resources");
 
-    // Create bundle structure
     fs::create_dir_all(&macos_path).expect("Failed to create MacOS dir");
     fs::create_dir_all(&resources_path).expect("Failed to create Resources dir");
 
-    // Build the Go binary into the bundle
     let binary_path = macos_path.join(name);
-    run_go_build(&binary_path);
+    run_go_build(&binary_path, manifest, "darwin", "arm64");
+    copy_assets(&resources_path.join("assets"), manifest);
 
-    // Copy assets into Resources
-    copy_assets(&resources_path.join("assets"));
+    let identifier = profile
+        .and_then(|p| p.identifier.clone())
+        .unwrap_or_else(|| format!("com.mirgo.{name}"));
 
-    // Create Info.plist
     let plist = format!(
         r#"<?xml version="1.0" encoding="UTF-8"?>
 <!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
@@ -57,7 +88,7 @@ fn build_macos_app(name: &str, build_dir: &Path) {
     <key>CFBundleExecutable</key>
     <string>{name}</string>
     <key>CFBundleIdentifier</key>
-    <string>com.mirgo.{name}</string>
+    <string>{identifier}</string>
     <key>CFBundleName</key>
     <string>{name}</string>
     <key>CFBundlePackageType</key>
@@ -77,25 +108,194 @@ fn build_macos_app(name: &str, build_dir: &Path) {
     println!("Double-click to run or drag to Applications!");
 }
 
-#[cfg(not(target_os = "macos"))]
-fn build_binary(name: &str, build_dir: &Path) {
-    let output_path = build_dir.join(name);
-    run_go_build(&output_path);
-    copy_assets(&build_dir.join("assets"));
+fn build_windows(name: &str, build_dir: &Path, manifest: &Manifest, profile: Option<&Profile>) {
+    let out_dir = build_dir.join("windows");
+    if let Err(e) = fs::create_dir_all(&out_dir) {
+        eprintln!("Error creating {}: {e}", out_dir.display());
+        process::exit(1);
+    }
+
+    if let Some(icon) = profile.and_then(|p| p.icon.as_deref()) {
+        embed_windows_resource(icon, manifest);
+    }
+
+    let binary_path = out_dir.join(format!("{name}.exe"));
+    run_go_build(&binary_path, manifest, "windows", "amd64");
+    copy_assets(&out_dir.join("assets"), manifest);
 
     println!("\nBuild complete!");
-    println!("Run with: cd build && ./{name}");
+    println!("Created: {}", binary_path.display());
+}
+
+/// Generates a `.syso` resource file next to the entrypoint via `rsrc`, so
+/// the next `go build` links the icon into the `.exe` automatically. Only
+/// the icon is passed to `rsrc`; no version-info resource is generated. Go picks up any `*.syso` sitting in the main package's
+/// directory without further flags, but also applies its implicit
+/// `_GOOS`/`_GOARCH` file-suffix build constraints to `.syso` files just
+/// like `.go` files — so the file is named `rsrc_windows_amd64.syso` to
+/// keep it out of the linux/wasm builds that may run in the same pass
+/// (`build --target windows,linux,...`), rather than a bare `rsrc.syso`
+/// that every subsequent `go build` of the package would pick up.
+fn embed_windows_resource(icon_path: &str, manifest: &Manifest) {
+    println!("Embedding icon resource via rsrc...");
+    let syso_path = Path::new(&manifest.entrypoint).join("rsrc_windows_amd64.syso");
+    let status = Command::new("go")
+        .args([
+            "run",
+            "github.com/akavel/rsrc@latest",
+            "-ico",
+            icon_path,
+            "-o",
+            syso_path.to_str().unwrap(),
+        ])
+        .status();
+
+    match status {
+        Ok(s) if s.success() => {}
+        Ok(s) => eprintln!(
+            "Warning: rsrc exited with code {}, continuing without embedded icon",
+            s.code().unwrap_or(-1)
+        ),
+        Err(e) => eprintln!("Warning: failed to run rsrc ({e}), continuing without embedded icon"),
+    }
+}
+
+/// Produces an AppDir: the standard AppImage staging layout
+/// (`usr/bin/<name>`, a `.desktop` launcher, an `AppRun` entrypoint). Run
+/// `appimagetool` on the result to produce a distributable `.AppImage`.
+fn build_linux_appdir(name: &str, build_dir: &Path, manifest: &Manifest, profile: Option<&Profile>) {
+    let app_dir = build_dir.join(format!("{name}.AppDir"));
+    let bin_dir = app_dir.join("usr/bin");
+    if let Err(e) = fs::create_dir_all(&bin_dir) {
+        eprintln!("Error creating {}: {e}", bin_dir.display());
+        process::exit(1);
+    }
+
+    let binary_path = bin_dir.join(name);
+    run_go_build(&binary_path, manifest, "linux", "amd64");
+    copy_assets(&app_dir.join("usr/share").join(name).join("assets"), manifest);
+
+    let identifier = profile
+        .and_then(|p| p.identifier.clone())
+        .unwrap_or_else(|| format!("com.mirgo.{name}"));
+    let desktop = format!(
+        "[Desktop Entry]\nType=Application\nName={name}\nExec={name}\nIcon={name}\nCategories=Game;\nX-Mirgo-Identifier={identifier}\n"
+    );
+    if let Err(e) = fs::write(app_dir.join(format!("{name}.desktop")), desktop) {
+        eprintln!("Error writing .desktop file: {e}");
+        process::exit(1);
+    }
+
+    if let Some(icon) = profile.and_then(|p| p.icon.as_deref()) {
+        if let Err(e) = fs::copy(icon, app_dir.join(format!("{name}.png"))) {
+            eprintln!("Warning: failed to copy icon {icon}: {e}");
+        }
+    }
+
+    let apprun_path = app_dir.join("AppRun");
+    let apprun = format!(
+        "#!/bin/sh\nHERE=\"$(dirname \"$(readlink -f \"$0\")\")\"\nexec \"$HERE/usr/bin/{name}\" \"$@\"\n"
+    );
+    if let Err(e) = fs::write(&apprun_path, apprun) {
+        eprintln!("Error writing AppRun: {e}");
+        process::exit(1);
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if let Ok(metadata) = fs::metadata(&apprun_path) {
+            let mut perms = metadata.permissions();
+            perms.set_mode(0o755);
+            let _ = fs::set_permissions(&apprun_path, perms);
+        }
+    }
+
+    println!("\nBuild complete!");
+    println!("Created: {}", app_dir.display());
+    println!("Run appimagetool on it to produce a distributable .AppImage");
+}
+
+/// Builds a `GOOS=js GOARCH=wasm` binary plus the browser shell needed to
+/// run it: the Go runtime's `wasm_exec.js` glue and a minimal `index.html`.
+fn build_wasm(name: &str, build_dir: &Path, manifest: &Manifest) {
+    let out_dir = build_dir.join("wasm");
+    if let Err(e) = fs::create_dir_all(&out_dir) {
+        eprintln!("Error creating {}: {e}", out_dir.display());
+        process::exit(1);
+    }
+
+    let wasm_path = out_dir.join("main.wasm");
+    run_go_build(&wasm_path, manifest, "js", "wasm");
+    copy_wasm_exec(&out_dir);
+    write_index_html(&out_dir, name);
+    copy_assets(&out_dir.join("assets"), manifest);
+
+    println!("\nBuild complete!");
+    println!("Created: {}", out_dir.display());
+    println!("Serve with: cd {} && python3 -m http.server", out_dir.display());
+}
+
+fn copy_wasm_exec(out_dir: &Path) {
+    let goroot = match Command::new("go").args(["env", "GOROOT"]).output() {
+        Ok(o) if o.status.success() => String::from_utf8_lossy(&o.stdout).trim().to_string(),
+        _ => {
+            eprintln!("Warning: could not determine GOROOT, skipping wasm_exec.js");
+            return;
+        }
+    };
+
+    let candidates = [
+        Path::new(&goroot).join("lib/wasm/wasm_exec.js"),
+        Path::new(&goroot).join("misc/wasm/wasm_exec.js"),
+    ];
+
+    for candidate in candidates {
+        if candidate.exists() {
+            if let Err(e) = fs::copy(&candidate, out_dir.join("wasm_exec.js")) {
+                eprintln!("Warning: failed to copy wasm_exec.js: {e}");
+            }
+            return;
+        }
+    }
+
+    eprintln!("Warning: wasm_exec.js not found under GOROOT, skipping");
+}
+
+fn write_index_html(out_dir: &Path, name: &str) {
+    let html = format!(
+        r#"<!DOCTYPE html>
+<html>
+<head><meta charset="utf-8"><title>{name}</title></head>
+<body>
+<script src="wasm_exec.js"></script>
+<script>
+const go = new Go();
+WebAssembly.instantiateStreaming(fetch("main.wasm"), go.importObject).then((result) => {{
+    go.run(result.instance);
+}});
+</script>
+</body>
+</html>
+"#
+    );
+    if let Err(e) = fs::write(out_dir.join("index.html"), html) {
+        eprintln!("Error writing index.html: {e}");
+        process::exit(1);
+    }
 }
 
-fn run_go_build(output_path: &Path) {
+fn run_go_build(output_path: &Path, manifest: &Manifest, goos: &str, goarch: &str) {
     let status = Command::new("go")
+        .env("GOOS", goos)
+        .env("GOARCH", goarch)
         .args([
             "build",
             "-tags",
             "game",
             "-o",
             output_path.to_str().unwrap(),
-            "./cmd/test3d",
+            &manifest.entrypoint,
         ])
         .status();
 
@@ -117,8 +317,8 @@ fn run_go_build(output_path: &Path) {
     }
 }
 
-fn copy_assets(dst: &Path) {
-    let assets_src = Path::new("assets");
+fn copy_assets(dst: &Path, manifest: &Manifest) {
+    let assets_src = Path::new(&manifest.assets_dir);
 
     if assets_src.exists() {
         println!("Copying assets...");
@@ -0,0 +1,330 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use serde_json::{json, Value};
+
+/// GLTF component type constants (from the glTF 2.0 spec).
+pub const BYTE: u64 = 5120;
+pub const UNSIGNED_BYTE: u64 = 5121;
+pub const SHORT: u64 = 5122;
+pub const UNSIGNED_SHORT: u64 = 5123;
+pub const UNSIGNED_INT: u64 = 5125;
+pub const FLOAT: u64 = 5126;
+
+/// A loaded `.gltf` + its external `.bin` buffer, kept in memory together so
+/// every operation in this module (flip, recompute-normals, gen-tangents,
+/// weld) can read and patch both sides through the same path.
+pub struct Gltf {
+    pub json: Value,
+    pub gltf_path: PathBuf,
+    pub bin_path: PathBuf,
+    pub bin: Vec<u8>,
+}
+
+impl Gltf {
+    /// Loads the `.gltf` at `path` (or, if `path` is a directory, the first
+    /// `.gltf` file found inside it) along with its referenced `.bin`.
+    pub fn load(path: &str) -> Gltf {
+        let mut gltf_path = Path::new(path).to_path_buf();
+        if !gltf_path.exists() {
+            eprintln!("Error: file not found: {path}");
+            process::exit(1);
+        }
+
+        if gltf_path.is_dir() {
+            let mut found = None;
+            if let Ok(entries) = fs::read_dir(&gltf_path) {
+                for entry in entries.flatten() {
+                    if entry.path().extension().map(|e| e == "gltf").unwrap_or(false) {
+                        found = Some(entry.path());
+                        break;
+                    }
+                }
+            }
+            match found {
+                Some(p) => {
+                    println!("Found GLTF file: {}", p.display());
+                    gltf_path = p;
+                }
+                None => {
+                    eprintln!("Error: no .gltf file found in directory {path}");
+                    process::exit(1);
+                }
+            }
+        }
+
+        let content = match fs::read_to_string(&gltf_path) {
+            Ok(c) => c,
+            Err(e) => {
+                eprintln!("Error reading file: {e}");
+                process::exit(1);
+            }
+        };
+
+        let json: Value = match serde_json::from_str(&content) {
+            Ok(v) => v,
+            Err(e) => {
+                eprintln!("Error parsing GLTF JSON: {e}");
+                process::exit(1);
+            }
+        };
+
+        let bin_path = if let Some(buffers) = json.get("buffers").and_then(|b| b.as_array()) {
+            if let Some(uri) = buffers.first().and_then(|b| b.get("uri")).and_then(|u| u.as_str()) {
+                gltf_path.parent().unwrap_or(Path::new(".")).join(uri)
+            } else {
+                eprintln!("Error: no buffer URI found in GLTF");
+                process::exit(1);
+            }
+        } else {
+            eprintln!("Error: no buffers found in GLTF");
+            process::exit(1);
+        };
+
+        let bin = match fs::read(&bin_path) {
+            Ok(d) => d,
+            Err(e) => {
+                eprintln!("Error reading binary file {}: {e}", bin_path.display());
+                process::exit(1);
+            }
+        };
+
+        Gltf { json, gltf_path, bin_path, bin }
+    }
+
+    /// Writes the (possibly patched) `.bin` back out. Operations that only
+    /// rewrite existing accessor bytes in place (flip, recompute-normals)
+    /// never touch `json`, so this is all they need.
+    pub fn save_bin(&self) {
+        if let Err(e) = fs::write(&self.bin_path, &self.bin) {
+            eprintln!("Error writing binary file: {e}");
+            process::exit(1);
+        }
+    }
+
+    /// Writes both the `.bin` and the `.gltf` back out. Needed by
+    /// operations that add accessors/bufferViews (gen-tangents, weld).
+    pub fn save_all(&self, gltf_path: &str) {
+        self.save_bin();
+        let pretty = match serde_json::to_string_pretty(&self.json) {
+            Ok(s) => s,
+            Err(e) => {
+                eprintln!("Error serializing GLTF JSON: {e}");
+                process::exit(1);
+            }
+        };
+        let mut target = Path::new(gltf_path).to_path_buf();
+        if target.is_dir() {
+            target = self.gltf_path.clone();
+        }
+        if let Err(e) = fs::write(&target, pretty) {
+            eprintln!("Error writing GLTF file: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// A resolved view over one accessor: everything needed to read or write its
+/// elements directly in the `.bin` buffer.
+pub struct Accessor {
+    pub count: usize,
+    pub component_type: u64,
+    pub accessor_type: String,
+    /// Absolute byte offset of element 0 in the `.bin` buffer.
+    pub byte_offset: usize,
+    /// Byte distance between consecutive elements.
+    pub byte_stride: usize,
+}
+
+/// Number of components per element for a glTF accessor `type` string.
+pub fn num_components(accessor_type: &str) -> usize {
+    match accessor_type {
+        "SCALAR" => 1,
+        "VEC2" => 2,
+        "VEC3" => 3,
+        "VEC4" => 4,
+        "MAT2" => 4,
+        "MAT3" => 9,
+        "MAT4" => 16,
+        _ => 0,
+    }
+}
+
+/// Byte size of a single component for a glTF `componentType` constant.
+pub fn component_size(component_type: u64) -> usize {
+    match component_type {
+        BYTE | UNSIGNED_BYTE => 1,
+        SHORT | UNSIGNED_SHORT => 2,
+        UNSIGNED_INT | FLOAT => 4,
+        _ => 0,
+    }
+}
+
+/// Resolves accessor `accessor_idx` into an `Accessor`, following its
+/// `bufferView` to compute an absolute byte offset and a stride (defaulting
+/// to tightly-packed when the bufferView doesn't specify one).
+pub fn read_accessor(gltf: &Value, accessor_idx: usize) -> Option<Accessor> {
+    let accessor = gltf.get("accessors")?.as_array()?.get(accessor_idx)?;
+
+    let buffer_view_idx = accessor.get("bufferView")?.as_u64()? as usize;
+    let count = accessor.get("count")?.as_u64()? as usize;
+    let component_type = accessor.get("componentType")?.as_u64()?;
+    let accessor_type = accessor.get("type")?.as_str()?.to_string();
+
+    let buffer_view = gltf.get("bufferViews")?.as_array()?.get(buffer_view_idx)?;
+    let bv_byte_offset = buffer_view.get("byteOffset").and_then(|o| o.as_u64()).unwrap_or(0) as usize;
+    let accessor_byte_offset = accessor.get("byteOffset").and_then(|o| o.as_u64()).unwrap_or(0) as usize;
+
+    let element_size = num_components(&accessor_type) * component_size(component_type);
+    let byte_stride = buffer_view
+        .get("byteStride")
+        .and_then(|s| s.as_u64())
+        .map(|s| s as usize)
+        .unwrap_or(element_size);
+
+    Some(Accessor {
+        count,
+        component_type,
+        accessor_type,
+        byte_offset: bv_byte_offset + accessor_byte_offset,
+        byte_stride,
+    })
+}
+
+pub fn read_f32(bin: &[u8], offset: usize) -> f32 {
+    if offset + 4 > bin.len() {
+        eprintln!("Warning: read past end of buffer at offset {offset}");
+        return 0.0;
+    }
+    f32::from_le_bytes(bin[offset..offset + 4].try_into().unwrap())
+}
+
+pub fn write_f32(bin: &mut [u8], offset: usize, value: f32) {
+    if offset + 4 > bin.len() {
+        eprintln!("Warning: write past end of buffer at offset {offset}");
+        return;
+    }
+    bin[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+}
+
+pub fn read_vec(bin: &[u8], offset: usize, components: usize) -> Vec<f32> {
+    (0..components).map(|i| read_f32(bin, offset + i * 4)).collect()
+}
+
+pub fn write_vec(bin: &mut [u8], offset: usize, values: &[f32]) {
+    for (i, v) in values.iter().enumerate() {
+        write_f32(bin, offset + i * 4, *v);
+    }
+}
+
+/// Reads one index out of an indices accessor, which may use any of the
+/// three integer component types the glTF spec allows.
+pub fn read_index(bin: &[u8], offset: usize, component_type: u64) -> u32 {
+    match component_type {
+        UNSIGNED_BYTE => bin.get(offset).copied().unwrap_or(0) as u32,
+        UNSIGNED_SHORT => {
+            if offset + 2 > bin.len() {
+                return 0;
+            }
+            u16::from_le_bytes(bin[offset..offset + 2].try_into().unwrap()) as u32
+        }
+        UNSIGNED_INT => {
+            if offset + 4 > bin.len() {
+                return 0;
+            }
+            u32::from_le_bytes(bin[offset..offset + 4].try_into().unwrap())
+        }
+        other => {
+            eprintln!("Warning: unsupported index componentType {other}");
+            0
+        }
+    }
+}
+
+/// Reads every index out of the accessor at `indices_accessor_idx` as a flat
+/// `u32` list.
+pub fn read_indices(gltf: &Value, bin: &[u8], indices_accessor_idx: usize) -> Vec<u32> {
+    let accessor = match read_accessor(gltf, indices_accessor_idx) {
+        Some(a) => a,
+        None => return Vec::new(),
+    };
+
+    (0..accessor.count)
+        .map(|i| read_index(bin, accessor.byte_offset + i * accessor.byte_stride, accessor.component_type))
+        .collect()
+}
+
+/// Appends `data` as a new bufferView on buffer 0, extending `bin` and
+/// bumping `buffers[0].byteLength` to match. Returns the new bufferView
+/// index.
+pub fn append_buffer_view(gltf: &mut Value, bin: &mut Vec<u8>, data: &[u8]) -> usize {
+    let byte_offset = bin.len();
+    bin.extend_from_slice(data);
+
+    let buffer_views = gltf["bufferViews"].as_array_mut().expect("bufferViews must be an array");
+    let index = buffer_views.len();
+    buffer_views.push(json!({
+        "buffer": 0,
+        "byteOffset": byte_offset,
+        "byteLength": data.len(),
+    }));
+
+    if let Some(buffers) = gltf["buffers"].as_array_mut() {
+        if let Some(buffer0) = buffers.get_mut(0) {
+            buffer0["byteLength"] = json!(bin.len());
+        }
+    }
+
+    index
+}
+
+/// glTF primitive `mode` value for a triangle list, the only topology the
+/// normal/tangent/weld operations in this module understand.
+pub const TRIANGLES: u64 = 4;
+
+/// Whether `primitive`'s `mode` is `TRIANGLES` (mode defaults to `TRIANGLES`
+/// per the glTF spec when omitted). Operations that treat a primitive's
+/// indices/vertices as an implicit triangle list must check this first —
+/// otherwise a `TRIANGLE_STRIP`/`TRIANGLE_FAN`/`LINES`/`POINTS` primitive
+/// would silently get garbage normals, tangents, or welding instead of a
+/// clear skip.
+pub fn is_triangle_list(primitive: &Value) -> bool {
+    primitive.get("mode").and_then(|m| m.as_u64()).unwrap_or(TRIANGLES) == TRIANGLES
+}
+
+/// Returns `(mesh_index, primitive_index)` for every primitive in the
+/// document, so operations can revisit a primitive by path for mutation
+/// after an earlier read-only pass.
+pub fn primitive_paths(gltf: &Value) -> Vec<(usize, usize)> {
+    let mut paths = Vec::new();
+    if let Some(meshes) = gltf.get("meshes").and_then(|m| m.as_array()) {
+        for (mesh_idx, mesh) in meshes.iter().enumerate() {
+            if let Some(primitives) = mesh.get("primitives").and_then(|p| p.as_array()) {
+                for prim_idx in 0..primitives.len() {
+                    paths.push((mesh_idx, prim_idx));
+                }
+            }
+        }
+    }
+    paths
+}
+
+/// Appends a new accessor over `buffer_view` and returns its index.
+pub fn add_accessor(
+    gltf: &mut Value,
+    buffer_view: usize,
+    component_type: u64,
+    accessor_type: &str,
+    count: usize,
+) -> usize {
+    let accessors = gltf["accessors"].as_array_mut().expect("accessors must be an array");
+    let index = accessors.len();
+    accessors.push(json!({
+        "bufferView": buffer_view,
+        "componentType": component_type,
+        "count": count,
+        "type": accessor_type,
+    }));
+    index
+}
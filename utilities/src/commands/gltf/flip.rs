@@ -0,0 +1,42 @@
+use super::accessor::{self, Gltf};
+
+pub fn run(path: &str) {
+    let mut gltf = Gltf::load(path);
+    let mut normals_flipped = 0;
+
+    for (mesh_idx, prim_idx) in accessor::primitive_paths(&gltf.json) {
+        let normal_idx = gltf.json["meshes"][mesh_idx]["primitives"][prim_idx]["attributes"]["NORMAL"]
+            .as_u64()
+            .map(|n| n as usize);
+
+        let Some(normal_idx) = normal_idx else { continue };
+        normals_flipped += flip_accessor(&gltf.json, normal_idx, &mut gltf.bin);
+    }
+
+    if normals_flipped == 0 {
+        println!("No normals found to flip in {path}");
+        return;
+    }
+
+    gltf.save_bin();
+    println!("Flipped {normals_flipped} normal vectors in {}", gltf.bin_path.display());
+}
+
+fn flip_accessor(gltf_json: &serde_json::Value, accessor_idx: usize, bin: &mut [u8]) -> usize {
+    let Some(acc) = accessor::read_accessor(gltf_json, accessor_idx) else {
+        return 0;
+    };
+
+    if acc.accessor_type != "VEC3" || acc.component_type != accessor::FLOAT {
+        eprintln!("Warning: NORMAL accessor is not VEC3 float, skipping");
+        return 0;
+    }
+
+    for i in 0..acc.count {
+        let offset = acc.byte_offset + i * acc.byte_stride;
+        let v = accessor::read_vec(bin, offset, 3);
+        accessor::write_vec(bin, offset, &[-v[0], -v[1], -v[2]]);
+    }
+
+    acc.count
+}
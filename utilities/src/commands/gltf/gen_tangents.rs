@@ -0,0 +1,180 @@
+use serde_json::json;
+
+use super::accessor::{self, Gltf};
+
+/// Computes a per-vertex TANGENT (VEC4, with handedness in `w`) for every
+/// primitive that has POSITION, NORMAL and TEXCOORD_0, using the standard
+/// Lengyel method, then appends it as a new accessor/bufferView and patches
+/// the primitive's attributes to reference it.
+pub fn run(path: &str) {
+    let mut gltf = Gltf::load(path);
+    let mut primitives_updated = 0;
+
+    for (mesh_idx, prim_idx) in accessor::primitive_paths(&gltf.json) {
+        let primitive = &gltf.json["meshes"][mesh_idx]["primitives"][prim_idx];
+        let position_idx = primitive["attributes"]["POSITION"].as_u64().map(|n| n as usize);
+        let normal_idx = primitive["attributes"]["NORMAL"].as_u64().map(|n| n as usize);
+        let texcoord_idx = primitive["attributes"]["TEXCOORD_0"].as_u64().map(|n| n as usize);
+        let indices_idx = primitive["indices"].as_u64().map(|n| n as usize);
+
+        let (Some(position_idx), Some(normal_idx), Some(texcoord_idx)) = (position_idx, normal_idx, texcoord_idx)
+        else {
+            continue;
+        };
+
+        if !accessor::is_triangle_list(primitive) {
+            eprintln!("Warning: primitive {mesh_idx}/{prim_idx} is not a triangle list, skipping");
+            continue;
+        }
+
+        let Some(tangents) = compute_tangents(&gltf.json, position_idx, normal_idx, texcoord_idx, indices_idx, &gltf.bin)
+        else {
+            continue;
+        };
+
+        let mut bytes = Vec::with_capacity(tangents.len() * 16);
+        for t in &tangents {
+            bytes.extend_from_slice(&t[0].to_le_bytes());
+            bytes.extend_from_slice(&t[1].to_le_bytes());
+            bytes.extend_from_slice(&t[2].to_le_bytes());
+            bytes.extend_from_slice(&t[3].to_le_bytes());
+        }
+
+        let buffer_view = accessor::append_buffer_view(&mut gltf.json, &mut gltf.bin, &bytes);
+        let accessor_idx = accessor::add_accessor(&mut gltf.json, buffer_view, accessor::FLOAT, "VEC4", tangents.len());
+        gltf.json["meshes"][mesh_idx]["primitives"][prim_idx]["attributes"]["TANGENT"] = json!(accessor_idx);
+        primitives_updated += 1;
+    }
+
+    if primitives_updated == 0 {
+        println!("No primitives with POSITION+NORMAL+TEXCOORD_0 found in {path}");
+        return;
+    }
+
+    gltf.save_all(path);
+    println!("Generated tangents for {primitives_updated} primitive(s) in {}", gltf.bin_path.display());
+}
+
+fn compute_tangents(
+    gltf_json: &serde_json::Value,
+    position_idx: usize,
+    normal_idx: usize,
+    texcoord_idx: usize,
+    indices_idx: Option<usize>,
+    bin: &[u8],
+) -> Option<Vec<[f32; 4]>> {
+    let positions = accessor::read_accessor(gltf_json, position_idx)?;
+    let normals = accessor::read_accessor(gltf_json, normal_idx)?;
+    let texcoords = accessor::read_accessor(gltf_json, texcoord_idx)?;
+
+    if positions.accessor_type != "VEC3" || normals.accessor_type != "VEC3" || texcoords.accessor_type != "VEC2" {
+        eprintln!("Warning: POSITION/NORMAL must be VEC3 and TEXCOORD_0 must be VEC2, skipping primitive");
+        return None;
+    }
+
+    let vertex_count = positions.count;
+    let read3 = |acc: &accessor::Accessor, i: usize| -> [f32; 3] {
+        let v = accessor::read_vec(bin, acc.byte_offset + i * acc.byte_stride, 3);
+        [v[0], v[1], v[2]]
+    };
+    let read2 = |acc: &accessor::Accessor, i: usize| -> [f32; 2] {
+        let v = accessor::read_vec(bin, acc.byte_offset + i * acc.byte_stride, 2);
+        [v[0], v[1]]
+    };
+
+    let triangles: Vec<[usize; 3]> = match indices_idx {
+        Some(idx) => accessor::read_indices(gltf_json, bin, idx)
+            .chunks(3)
+            .filter(|c| c.len() == 3)
+            .map(|c| [c[0] as usize, c[1] as usize, c[2] as usize])
+            .collect(),
+        None => (0..vertex_count / 3).map(|t| [t * 3, t * 3 + 1, t * 3 + 2]).collect(),
+    };
+
+    let mut tangent_sum = vec![[0f32; 3]; vertex_count];
+    let mut bitangent_sum = vec![[0f32; 3]; vertex_count];
+
+    for tri in &triangles {
+        let [i0, i1, i2] = *tri;
+        if i0 >= vertex_count || i1 >= vertex_count || i2 >= vertex_count {
+            continue;
+        }
+
+        let p0 = read3(&positions, i0);
+        let p1 = read3(&positions, i1);
+        let p2 = read3(&positions, i2);
+        let uv0 = read2(&texcoords, i0);
+        let uv1 = read2(&texcoords, i1);
+        let uv2 = read2(&texcoords, i2);
+
+        let edge1 = sub3(p1, p0);
+        let edge2 = sub3(p2, p0);
+        let delta_uv1 = sub2(uv1, uv0);
+        let delta_uv2 = sub2(uv2, uv0);
+
+        let denom = delta_uv1[0] * delta_uv2[1] - delta_uv2[0] * delta_uv1[1];
+        if denom.abs() < f32::EPSILON {
+            continue;
+        }
+        let r = 1.0 / denom;
+
+        let tangent = scale3(sub3(scale3(edge1, delta_uv2[1]), scale3(edge2, delta_uv1[1])), r);
+        let bitangent = scale3(sub3(scale3(edge2, delta_uv1[0]), scale3(edge1, delta_uv2[0])), r);
+
+        for i in [i0, i1, i2] {
+            tangent_sum[i] = add3(tangent_sum[i], tangent);
+            bitangent_sum[i] = add3(bitangent_sum[i], bitangent);
+        }
+    }
+
+    let mut tangents = Vec::with_capacity(vertex_count);
+    for i in 0..vertex_count {
+        let n = read3(&normals, i);
+        let t = tangent_sum[i];
+
+        // Gram-Schmidt orthogonalize against the normal.
+        let t_ortho = normalize3(sub3(t, scale3(n, dot3(n, t))));
+        let handedness = if dot3(cross3(n, t), bitangent_sum[i]) < 0.0 { -1.0 } else { 1.0 };
+
+        tangents.push([t_ortho[0], t_ortho[1], t_ortho[2], handedness]);
+    }
+
+    Some(tangents)
+}
+
+fn sub3(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn add3(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn scale3(a: [f32; 3], s: f32) -> [f32; 3] {
+    [a[0] * s, a[1] * s, a[2] * s]
+}
+
+fn dot3(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross3(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn normalize3(v: [f32; 3]) -> [f32; 3] {
+    let len = dot3(v, v).sqrt();
+    if len < f32::EPSILON {
+        [0.0, 0.0, 1.0]
+    } else {
+        [v[0] / len, v[1] / len, v[2] / len]
+    }
+}
+
+fn sub2(a: [f32; 2], b: [f32; 2]) -> [f32; 2] {
+    [a[0] - b[0], a[1] - b[1]]
+}
@@ -0,0 +1,5 @@
+pub mod accessor;
+pub mod flip;
+pub mod gen_tangents;
+pub mod recompute_normals;
+pub mod weld;
@@ -0,0 +1,126 @@
+use super::accessor::{self, Gltf};
+
+/// Rebuilds a primitive's NORMAL accessor by averaging face normals over its
+/// triangles, computed from POSITION and (if present) the indices accessor.
+/// Primitives without an existing NORMAL accessor are skipped, since this
+/// only recomputes values in place rather than allocating a new accessor.
+pub fn run(path: &str) {
+    let mut gltf = Gltf::load(path);
+    let mut primitives_updated = 0;
+
+    for (mesh_idx, prim_idx) in accessor::primitive_paths(&gltf.json) {
+        let primitive = &gltf.json["meshes"][mesh_idx]["primitives"][prim_idx];
+        let position_idx = primitive["attributes"]["POSITION"].as_u64().map(|n| n as usize);
+        let normal_idx = primitive["attributes"]["NORMAL"].as_u64().map(|n| n as usize);
+        let indices_idx = primitive["indices"].as_u64().map(|n| n as usize);
+
+        let (Some(position_idx), Some(normal_idx)) = (position_idx, normal_idx) else {
+            continue;
+        };
+
+        if !accessor::is_triangle_list(primitive) {
+            eprintln!("Warning: primitive {mesh_idx}/{prim_idx} is not a triangle list, skipping");
+            continue;
+        }
+
+        if recompute_primitive(&gltf.json, position_idx, normal_idx, indices_idx, &mut gltf.bin) {
+            primitives_updated += 1;
+        }
+    }
+
+    if primitives_updated == 0 {
+        println!("No primitives with POSITION+NORMAL found in {path}");
+        return;
+    }
+
+    gltf.save_bin();
+    println!("Recomputed normals for {primitives_updated} primitive(s) in {}", gltf.bin_path.display());
+}
+
+fn recompute_primitive(
+    gltf_json: &serde_json::Value,
+    position_idx: usize,
+    normal_idx: usize,
+    indices_idx: Option<usize>,
+    bin: &mut [u8],
+) -> bool {
+    let Some(positions) = accessor::read_accessor(gltf_json, position_idx) else {
+        return false;
+    };
+    let Some(normals) = accessor::read_accessor(gltf_json, normal_idx) else {
+        return false;
+    };
+
+    if positions.accessor_type != "VEC3" || normals.accessor_type != "VEC3" {
+        eprintln!("Warning: POSITION/NORMAL must be VEC3, skipping primitive");
+        return false;
+    }
+
+    let vertex_count = positions.count;
+    let read_position = |i: usize| -> [f32; 3] {
+        let offset = positions.byte_offset + i * positions.byte_stride;
+        let v = accessor::read_vec(bin, offset, 3);
+        [v[0], v[1], v[2]]
+    };
+
+    let triangles: Vec<[usize; 3]> = match indices_idx {
+        Some(idx) => accessor::read_indices(gltf_json, bin, idx)
+            .chunks(3)
+            .filter(|c| c.len() == 3)
+            .map(|c| [c[0] as usize, c[1] as usize, c[2] as usize])
+            .collect(),
+        None => (0..vertex_count / 3).map(|t| [t * 3, t * 3 + 1, t * 3 + 2]).collect(),
+    };
+
+    let mut accumulated = vec![[0f32; 3]; vertex_count];
+    for tri in &triangles {
+        let [i0, i1, i2] = *tri;
+        if i0 >= vertex_count || i1 >= vertex_count || i2 >= vertex_count {
+            continue;
+        }
+
+        let p0 = read_position(i0);
+        let p1 = read_position(i1);
+        let p2 = read_position(i2);
+        let edge1 = sub(p1, p0);
+        let edge2 = sub(p2, p0);
+        let face_normal = cross(edge1, edge2);
+
+        for i in [i0, i1, i2] {
+            accumulated[i] = add(accumulated[i], face_normal);
+        }
+    }
+
+    for i in 0..vertex_count {
+        let n = normalize(accumulated[i]);
+        let offset = normals.byte_offset + i * normals.byte_stride;
+        accessor::write_vec(bin, offset, &n);
+    }
+
+    true
+}
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn add(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn normalize(v: [f32; 3]) -> [f32; 3] {
+    let len = (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+    if len < f32::EPSILON {
+        [0.0, 0.0, 1.0]
+    } else {
+        [v[0] / len, v[1] / len, v[2] / len]
+    }
+}
@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+
+use serde_json::json;
+
+use super::accessor::{self, Gltf};
+
+/// Attribute names considered for vertex welding. Anything else on the
+/// primitive is left as-is (and therefore, if present, still sized for the
+/// original vertex count and misaligned with the rebuilt index buffer, since
+/// only these are remapped) — in practice POSITION/NORMAL/TEXCOORD_0/TANGENT
+/// cover the primitives this tool's other gltf operations produce.
+const WELDABLE_ATTRIBUTES: &[&str] = &["POSITION", "NORMAL", "TEXCOORD_0", "TEXCOORD_1", "TANGENT", "COLOR_0"];
+
+const DEFAULT_EPSILON: f32 = 1e-5;
+
+/// Merges vertices whose POSITION *and* every other float-valued weldable
+/// attribute are each within `epsilon` of one another, and rebuilds the
+/// index buffer (and every weldable attribute accessor) to reference the
+/// deduplicated vertex set. Matching on position alone would also merge a
+/// cube's split corners, since they share a position but disagree on
+/// per-face NORMAL/TEXCOORD_0. Components are compared by rounding each to
+/// a grid of `epsilon`-sized cells, so two values closer than `epsilon` that
+/// round into neighbouring cells are not merged.
+pub fn run(path: &str, epsilon: Option<f32>) {
+    let epsilon = epsilon.unwrap_or(DEFAULT_EPSILON);
+    let mut gltf = Gltf::load(path);
+    let mut primitives_welded = 0;
+    let mut vertices_removed = 0;
+
+    for (mesh_idx, prim_idx) in accessor::primitive_paths(&gltf.json) {
+        let primitive = &gltf.json["meshes"][mesh_idx]["primitives"][prim_idx];
+        let position_idx = primitive["attributes"]["POSITION"].as_u64().map(|n| n as usize);
+        let Some(position_idx) = position_idx else { continue };
+        let indices_idx = primitive["indices"].as_u64().map(|n| n as usize);
+
+        if !accessor::is_triangle_list(primitive) {
+            eprintln!("Warning: primitive {mesh_idx}/{prim_idx} is not a triangle list, skipping");
+            continue;
+        }
+
+        let attribute_indices: Vec<(&str, usize)> = WELDABLE_ATTRIBUTES
+            .iter()
+            .filter_map(|&name| primitive["attributes"][name].as_u64().map(|n| (name, n as usize)))
+            .collect();
+
+        let Some(result) = weld_primitive(&gltf.json, position_idx, &attribute_indices, indices_idx, epsilon, &gltf.bin)
+        else {
+            continue;
+        };
+
+        vertices_removed += result.original_count - result.unique_count;
+
+        for (name, bytes, component_type, accessor_type) in &result.attributes {
+            let buffer_view = accessor::append_buffer_view(&mut gltf.json, &mut gltf.bin, bytes);
+            let accessor_idx =
+                accessor::add_accessor(&mut gltf.json, buffer_view, *component_type, accessor_type, result.unique_count);
+            gltf.json["meshes"][mesh_idx]["primitives"][prim_idx]["attributes"][name] = json!(accessor_idx);
+        }
+
+        let indices_buffer_view = accessor::append_buffer_view(&mut gltf.json, &mut gltf.bin, &result.index_bytes);
+        let indices_accessor = accessor::add_accessor(
+            &mut gltf.json,
+            indices_buffer_view,
+            accessor::UNSIGNED_INT,
+            "SCALAR",
+            result.triangle_count * 3,
+        );
+        gltf.json["meshes"][mesh_idx]["primitives"][prim_idx]["indices"] = json!(indices_accessor);
+
+        primitives_welded += 1;
+    }
+
+    if primitives_welded == 0 {
+        println!("No primitives with POSITION found in {path}");
+        return;
+    }
+
+    gltf.save_all(path);
+    println!(
+        "Welded {primitives_welded} primitive(s), removing {vertices_removed} duplicate vertices, in {}",
+        gltf.bin_path.display()
+    );
+}
+
+struct WeldResult {
+    original_count: usize,
+    unique_count: usize,
+    triangle_count: usize,
+    attributes: Vec<(&'static str, Vec<u8>, u64, &'static str)>,
+    index_bytes: Vec<u8>,
+}
+
+fn weld_primitive(
+    gltf_json: &serde_json::Value,
+    position_idx: usize,
+    attribute_indices: &[(&'static str, usize)],
+    indices_idx: Option<usize>,
+    epsilon: f32,
+    bin: &[u8],
+) -> Option<WeldResult> {
+    let positions = accessor::read_accessor(gltf_json, position_idx)?;
+    if positions.accessor_type != "VEC3" {
+        eprintln!("Warning: POSITION must be VEC3, skipping primitive");
+        return None;
+    }
+
+    let original_count = positions.count;
+    let read_position = |i: usize| -> [f32; 3] {
+        let v = accessor::read_vec(bin, positions.byte_offset + i * positions.byte_stride, 3);
+        [v[0], v[1], v[2]]
+    };
+
+    // Vertices only merge if POSITION *and* every other weldable attribute
+    // agree within epsilon — otherwise a cube's per-face normals or a UV
+    // seam would collapse distinct corners into one, smearing shading and
+    // corrupting texture coordinates. Non-float attributes (e.g. a
+    // normalized UNSIGNED_BYTE COLOR_0) can't be compared this way and are
+    // left out of the key, but are still rebuilt below like every other
+    // weldable attribute.
+    let key_accessors: Vec<accessor::Accessor> = attribute_indices
+        .iter()
+        .filter(|&&(_, idx)| idx != position_idx)
+        .filter_map(|&(_, idx)| accessor::read_accessor(gltf_json, idx))
+        .filter(|acc| acc.component_type == accessor::FLOAT)
+        .collect();
+
+    let inv_eps = 1.0 / epsilon.max(f32::EPSILON);
+    let mut seen: HashMap<Vec<i64>, usize> = HashMap::new();
+    let mut remap = vec![0usize; original_count];
+    let mut unique_source_indices: Vec<usize> = Vec::new();
+
+    for i in 0..original_count {
+        let p = read_position(i);
+        let mut key: Vec<i64> = vec![
+            (p[0] * inv_eps).round() as i64,
+            (p[1] * inv_eps).round() as i64,
+            (p[2] * inv_eps).round() as i64,
+        ];
+        for acc in &key_accessors {
+            let components = accessor::num_components(&acc.accessor_type);
+            let values = accessor::read_vec(bin, acc.byte_offset + i * acc.byte_stride, components);
+            key.extend(values.iter().map(|v| (v * inv_eps).round() as i64));
+        }
+
+        let new_index = *seen.entry(key).or_insert_with(|| {
+            unique_source_indices.push(i);
+            unique_source_indices.len() - 1
+        });
+        remap[i] = new_index;
+    }
+
+    let original_indices: Vec<u32> = match indices_idx {
+        Some(idx) => accessor::read_indices(gltf_json, bin, idx),
+        None => (0..original_count as u32).collect(),
+    };
+
+    let new_indices: Vec<u32> = original_indices
+        .chunks(3)
+        .filter(|c| c.len() == 3)
+        .map(|c| [remap[c[0] as usize], remap[c[1] as usize], remap[c[2] as usize]])
+        .filter(|tri| tri[0] != tri[1] && tri[1] != tri[2] && tri[0] != tri[2])
+        .flat_map(|tri| tri.into_iter().map(|i| i as u32))
+        .collect();
+
+    let mut index_bytes = Vec::with_capacity(new_indices.len() * 4);
+    for i in &new_indices {
+        index_bytes.extend_from_slice(&i.to_le_bytes());
+    }
+
+    // Every weldable attribute present on the primitive gets rebuilt against
+    // `unique_source_indices`, not just the float ones used for the epsilon
+    // key above — a skipped attribute (e.g. a normalized UNSIGNED_BYTE
+    // COLOR_0) would otherwise stay sized for `original_count` vertices
+    // while POSITION/NORMAL/the index buffer move to the deduplicated set,
+    // silently misaligning its data against the new indices. Non-float data
+    // is copied as raw bytes in its own component type rather than decoded
+    // through `read_vec`, which only understands f32.
+    let mut attributes = Vec::new();
+    for &(name, accessor_idx) in attribute_indices {
+        let Some(acc) = accessor::read_accessor(gltf_json, accessor_idx) else {
+            continue;
+        };
+
+        let components = accessor::num_components(&acc.accessor_type);
+        let component_size = accessor::component_size(acc.component_type);
+        if components == 0 || component_size == 0 {
+            eprintln!("Warning: skipping attribute {name} with unsupported type during weld");
+            continue;
+        }
+
+        let accessor_type = match acc.accessor_type.as_str() {
+            "SCALAR" => "SCALAR",
+            "VEC2" => "VEC2",
+            "VEC3" => "VEC3",
+            "VEC4" => "VEC4",
+            other => {
+                eprintln!("Warning: skipping attribute {name} with unsupported type {other} during weld");
+                continue;
+            }
+        };
+
+        if acc.component_type == accessor::FLOAT {
+            let mut bytes = Vec::with_capacity(unique_source_indices.len() * components * 4);
+            for &source_index in &unique_source_indices {
+                let offset = acc.byte_offset + source_index * acc.byte_stride;
+                let values = accessor::read_vec(bin, offset, components);
+                for v in values {
+                    bytes.extend_from_slice(&v.to_le_bytes());
+                }
+            }
+            attributes.push((name, bytes, accessor::FLOAT, accessor_type));
+        } else {
+            let element_size = components * component_size;
+            let mut bytes = Vec::with_capacity(unique_source_indices.len() * element_size);
+            for &source_index in &unique_source_indices {
+                let offset = acc.byte_offset + source_index * acc.byte_stride;
+                match bin.get(offset..offset + element_size) {
+                    Some(slice) => bytes.extend_from_slice(slice),
+                    None => {
+                        eprintln!("Warning: read past end of buffer at offset {offset}");
+                        bytes.extend(std::iter::repeat(0u8).take(element_size));
+                    }
+                }
+            }
+            attributes.push((name, bytes, acc.component_type, accessor_type));
+        }
+    }
+
+    Some(WeldResult {
+        original_count,
+        unique_count: unique_source_indices.len(),
+        triangle_count: new_indices.len() / 3,
+        attributes,
+        index_bytes,
+    })
+}
@@ -0,0 +1,81 @@
+use std::fs;
+use std::path::Path;
+use std::process;
+
+use git2::{build::CheckoutBuilder, Repository};
+
+use crate::manifest::Manifest;
+
+/// Clones or updates every script package listed under `[dependencies]` in
+/// `mirgo.toml` into `<scripts_dir>/vendor/<name>`, then regenerates the
+/// blank-import file that wires them into the script registry.
+pub fn run(manifest: &Manifest) {
+    if manifest.dependencies.is_empty() {
+        println!("No script package dependencies declared in {}", crate::manifest::MANIFEST_FILE);
+        return;
+    }
+
+    let vendor_dir = Path::new(&manifest.scripts_dir).join("vendor");
+    if let Err(e) = fs::create_dir_all(&vendor_dir) {
+        eprintln!("Error creating {}: {e}", vendor_dir.display());
+        process::exit(1);
+    }
+
+    for (name, url) in &manifest.dependencies {
+        let dest = vendor_dir.join(name);
+        if dest.exists() {
+            println!("Updating {name}...");
+            match Repository::open(&dest).and_then(|repo| pull_latest(&repo)) {
+                Ok(()) => {}
+                Err(e) => {
+                    eprintln!("Error updating {name} at {}: {e}", dest.display());
+                    process::exit(1);
+                }
+            }
+        } else {
+            println!("Cloning {name} from {url}...");
+            if let Err(e) = Repository::clone(url, &dest) {
+                eprintln!("Error cloning {name}: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    write_vendor_registry(manifest);
+    println!("Installed {} script package(s)", manifest.dependencies.len());
+}
+
+fn pull_latest(repo: &Repository) -> Result<(), git2::Error> {
+    let mut remote = repo.find_remote("origin")?;
+    remote.fetch(&["HEAD"], None, None)?;
+
+    let fetch_head = repo.find_reference("FETCH_HEAD")?;
+    let target = repo.reference_to_annotated_commit(&fetch_head)?;
+
+    repo.set_head_detached(target.id())?;
+    repo.checkout_head(Some(CheckoutBuilder::new().force()))?;
+    Ok(())
+}
+
+/// Regenerates `<scripts_dir>/vendor_register.go`, a generated file that
+/// blank-imports every vendored script package so their `init()` calls
+/// (which call `engine.RegisterScript`) run without the game module having
+/// to list each package by hand.
+fn write_vendor_registry(manifest: &Manifest) {
+    let mut body = String::from(
+        "// Code generated by `mirgo-utils install`. DO NOT EDIT.\npackage scripts\n\nimport (\n",
+    );
+
+    let mut names: Vec<&String> = manifest.dependencies.keys().collect();
+    names.sort();
+    for name in names {
+        body.push_str(&format!("\t_ \"{}/{}/vendor/{name}\"\n", manifest.module, manifest.scripts_dir));
+    }
+    body.push_str(")\n");
+
+    let out_path = Path::new(&manifest.scripts_dir).join("vendor_register.go");
+    if let Err(e) = fs::write(&out_path, body) {
+        eprintln!("Error writing {}: {e}", out_path.display());
+        process::exit(1);
+    }
+}
@@ -0,0 +1,6 @@
+pub mod build;
+pub mod gltf;
+pub mod install;
+pub mod new;
+pub mod newscript;
+pub mod pack;
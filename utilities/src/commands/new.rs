@@ -0,0 +1,47 @@
+use std::fs;
+use std::path::Path;
+use std::process;
+
+const DEFAULT_MANIFEST: &str = r#"module = "test3d"
+scripts_dir = "internal/components/scripts"
+assets_dir = "assets"
+entrypoint = "./cmd/test3d"
+
+[profiles.dev]
+targets = ["macos"]
+
+[dependencies]
+"#;
+
+/// Scaffolds a fresh project: the default directory layout plus a
+/// `mirgo.toml` manifest pointing at it, so every other subcommand works
+/// out of the box without any extra configuration.
+pub fn run(project_name: &str) {
+    let root = Path::new(project_name);
+    if root.exists() {
+        eprintln!("Error: {} already exists", root.display());
+        process::exit(1);
+    }
+
+    for dir in [
+        root.join("internal/components/scripts"),
+        root.join("assets"),
+        root.join("cmd/test3d"),
+    ] {
+        if let Err(e) = fs::create_dir_all(&dir) {
+            eprintln!("Error creating {}: {e}", dir.display());
+            process::exit(1);
+        }
+    }
+
+    let manifest_path = root.join("mirgo.toml");
+    if let Err(e) = fs::write(&manifest_path, DEFAULT_MANIFEST) {
+        eprintln!("Error writing {}: {e}", manifest_path.display());
+        process::exit(1);
+    }
+
+    println!("Created new mirgo project in {}", root.display());
+    println!("Next steps:");
+    println!("  cd {project_name}");
+    println!("  mirgo-utils newscript MyScript");
+}
@@ -2,9 +2,7 @@ use std::fs;
 use std::path::Path;
 use std::process;
 
-const SCRIPTS_DIR: &str = "internal/components/scripts";
-
-const TEMPLATE: &str = r#"package scripts
+const HEADER: &str = r#"package scripts
 
 import "test3d/internal/engine"
 
@@ -12,18 +10,12 @@ type {{NAME}} struct {
 	engine.BaseComponent
 	Speed float32
 }
+"#;
 
-func (s *{{NAME}}) Update(deltaTime float32) {
-	g := s.GetGameObject()
-	if g == nil {
-		return
-	}
-	// TODO: implement behavior
-}
-
+const FOOTER: &str = r#"
 func init() {
 	engine.RegisterScript("{{NAME}}", {{LOWER}}Factory, {{LOWER}}Serializer)
-}
+{{CALLBACKS}}}
 
 func {{LOWER}}Factory(props map[string]any) engine.Component {
 	speed := float32(1)
@@ -44,12 +36,57 @@ func {{LOWER}}Serializer(c engine.Component) map[string]any {
 }
 "#;
 
-pub fn run(name: &str) {
+/// One supported lifecycle hook: the Go method `newscript` scaffolds for it,
+/// and (unless it's `Update`, which the engine already invokes through the
+/// `Component` interface every frame) the `engine.RegisterCallback` wiring
+/// that binds it to the named event.
+struct Hook {
+    key: &'static str,
+    method: &'static str,
+    params: &'static str,
+    body: &'static str,
+    callback: Option<&'static str>,
+}
+
+const HOOKS: &[Hook] = &[
+    Hook {
+        key: "update",
+        method: "Update",
+        params: "deltaTime float32",
+        body: "\tg := s.GetGameObject()\n\tif g == nil {\n\t\treturn\n\t}\n\t// TODO: implement behavior",
+        callback: None,
+    },
+    Hook {
+        key: "start",
+        method: "Start",
+        params: "",
+        body: "\t// TODO: implement behavior",
+        callback: Some("\tengine.RegisterCallback(\"{{NAME}}.Start\", func(c engine.Component) {\n\t\tif s, ok := c.(*{{NAME}}); ok {\n\t\t\ts.Start()\n\t\t}\n\t})\n"),
+    },
+    Hook {
+        key: "collision",
+        method: "OnCollision",
+        params: "other *engine.GameObject",
+        body: "\t// TODO: implement behavior",
+        callback: Some("\tengine.RegisterCallback(\"{{NAME}}.OnCollision\", func(c engine.Component, other *engine.GameObject) {\n\t\tif s, ok := c.(*{{NAME}}); ok {\n\t\t\ts.OnCollision(other)\n\t\t}\n\t})\n"),
+    },
+    Hook {
+        key: "destroy",
+        method: "OnDestroy",
+        params: "",
+        body: "\t// TODO: implement behavior",
+        callback: Some("\tengine.RegisterCallback(\"{{NAME}}.OnDestroy\", func(c engine.Component) {\n\t\tif s, ok := c.(*{{NAME}}); ok {\n\t\t\ts.OnDestroy()\n\t\t}\n\t})\n"),
+    },
+];
+
+pub fn run(name: &str, hooks: &[String], scripts_dir: &str) {
     if name.is_empty() || !name.chars().next().unwrap().is_uppercase() {
         eprintln!("Error: script name must start with an uppercase letter");
         process::exit(1);
     }
 
+    let selected = resolve_hooks(hooks);
+
     let lower = {
         let mut chars = name.chars();
         let first = chars.next().unwrap().to_lowercase().to_string();
@@ -57,16 +94,14 @@ pub fn run(name: &str) {
     };
 
     let filename = format!("{}.go", to_snake_case(name));
-    let out_path = Path::new(SCRIPTS_DIR).join(&filename);
+    let out_path = Path::new(scripts_dir).join(&filename);
 
     if out_path.exists() {
         eprintln!("Error: {} already exists", out_path.display());
         process::exit(1);
     }
 
-    let content = TEMPLATE
-        .replace("{{NAME}}", name)
-        .replace("{{LOWER}}", &lower);
+    let content = render(name, &lower, &selected);
 
     if let Err(e) = fs::write(&out_path, content) {
         eprintln!("Error writing file: {e}");
@@ -82,6 +117,50 @@ pub fn run(name: &str) {
     println!("  }}");
 }
 
+/// Maps the user-requested `--hooks` keys onto the supported `Hook` specs,
+/// in a deterministic, request order. An empty list falls back to the
+/// original single `Update` method, matching the tool's previous behavior.
+fn resolve_hooks(requested: &[String]) -> Vec<&'static Hook> {
+    if requested.is_empty() {
+        return vec![HOOKS.iter().find(|h| h.key == "update").unwrap()];
+    }
+
+    requested
+        .iter()
+        .map(|key| match HOOKS.iter().find(|h| h.key == key.as_str()) {
+            Some(hook) => hook,
+            None => {
+                let known: Vec<&str> = HOOKS.iter().map(|h| h.key).collect();
+                eprintln!("Error: unknown hook \"{key}\" (known hooks: {})", known.join(", "));
+                process::exit(1);
+            }
+        })
+        .collect()
+}
+
+fn render(name: &str, lower: &str, hooks: &[&'static Hook]) -> String {
+    let mut methods = String::new();
+    let mut callbacks = String::new();
+
+    for hook in hooks {
+        methods.push_str(&format!(
+            "\nfunc (s *{name}) {}({}) {{\n{}\n}}\n",
+            hook.method, hook.params, hook.body
+        ));
+        if let Some(callback) = hook.callback {
+            callbacks.push_str(&callback.replace("{{NAME}}", name));
+        }
+    }
+
+    let header = HEADER.replace("{{NAME}}", name);
+    let footer = FOOTER
+        .replace("{{CALLBACKS}}", &callbacks)
+        .replace("{{NAME}}", name)
+        .replace("{{LOWER}}", lower);
+
+    format!("{header}{methods}{footer}")
+}
+
 fn to_snake_case(s: &str) -> String {
     let mut result = String::new();
     for (i, c) in s.chars().enumerate() {
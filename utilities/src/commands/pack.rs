@@ -0,0 +1,602 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+/// Magic bytes identifying an `.mpak` archive, both at the start of the file
+/// and mirrored in the footer so `unpack`/`get` can validate without
+/// re-reading the header.
+const MAGIC: &[u8; 4] = b"MPK1";
+
+/// Size of the buzhash sliding window, in bytes.
+const WINDOW_SIZE: usize = 64;
+
+/// Default chunk size bounds. Average chunk size is driven by `DEFAULT_MASK_BITS`
+/// (≈ 2^DEFAULT_MASK_BITS bytes) but every chunk is clamped into this range.
+const DEFAULT_MIN_CHUNK: usize = 64 * 1024;
+const DEFAULT_MAX_CHUNK: usize = 4 * 1024 * 1024;
+
+/// Number of low bits of the rolling hash that must be zero to cut a chunk
+/// boundary. 20 bits gives an average chunk size around 1 MiB.
+const DEFAULT_MASK_BITS: u32 = 20;
+
+/// Size in bytes of one entry in the on-disk chunk catalog: digest + offset + length.
+const CHUNK_CATALOG_ENTRY_SIZE: usize = 32 + 8 + 4;
+
+/// Size in bytes of one entry in the on-disk path catalog: path hash + file entry offset.
+const PATH_CATALOG_ENTRY_SIZE: usize = 8 + 8;
+
+/// Size in bytes of the trailing footer.
+const FOOTER_SIZE: usize = 8 + 8 + 8 + 4 + 4;
+
+/// One unique chunk of content, addressed by its blake3 digest and located
+/// by its byte range in the blob region.
+struct ChunkRef {
+    digest: [u8; 32],
+    offset: u64,
+    length: u32,
+}
+
+/// A packed file: its path relative to the asset root, plus the ordered
+/// list of chunks that reconstruct it.
+struct FileEntry {
+    path: String,
+    chunks: Vec<ChunkRef>,
+}
+
+pub fn pack(src_dir: &str, out_path: &str) {
+    let src_dir = Path::new(src_dir);
+    if !src_dir.is_dir() {
+        eprintln!("Error: not a directory: {}", src_dir.display());
+        process::exit(1);
+    }
+
+    let existing = fs::read(out_path).ok();
+    let (blob_prefix_len, chunk_catalog_offset, chunk_catalog_count) = match &existing {
+        Some(data) => {
+            let footer = read_footer(data);
+            (footer.file_index_offset, footer.chunk_catalog_offset, footer.chunk_catalog_count)
+        }
+        None => (4, 0, 0),
+    };
+    let existing_blob = existing.as_deref().map(|d| &d[4..blob_prefix_len as usize]);
+
+    let mut files = Vec::new();
+    collect_files(src_dir, src_dir, &mut files);
+    files.sort();
+
+    let mut appended = Vec::new();
+    let mut new_chunks: HashMap<[u8; 32], (u64, u32)> = HashMap::new();
+    let mut entries = Vec::new();
+    let mut total_chunks = 0usize;
+    let mut reused_chunks = 0usize;
+    let existing_blob_len = blob_prefix_len - 4;
+
+    for rel_path in &files {
+        let full_path = src_dir.join(rel_path);
+        let data = match fs::read(&full_path) {
+            Ok(d) => d,
+            Err(e) => {
+                eprintln!("Error reading {}: {e}", full_path.display());
+                process::exit(1);
+            }
+        };
+
+        let mut chunks = Vec::new();
+        for (start, len) in split_chunks(&data, DEFAULT_MIN_CHUNK, DEFAULT_MAX_CHUNK, DEFAULT_MASK_BITS) {
+            total_chunks += 1;
+            let bytes = &data[start..start + len];
+            let digest = *blake3::hash(bytes).as_bytes();
+
+            let (offset, length) = if let Some(&existing_ref) = new_chunks.get(&digest) {
+                reused_chunks += 1;
+                existing_ref
+            } else if let Some(existing_data) = existing.as_deref() {
+                match find_chunk(existing_data, chunk_catalog_offset, chunk_catalog_count, &digest) {
+                    Some(existing_ref) => {
+                        reused_chunks += 1;
+                        existing_ref
+                    }
+                    None => {
+                        let offset = existing_blob_len + appended.len() as u64;
+                        appended.extend_from_slice(bytes);
+                        let reference = (offset, len as u32);
+                        new_chunks.insert(digest, reference);
+                        reference
+                    }
+                }
+            } else {
+                let offset = appended.len() as u64;
+                appended.extend_from_slice(bytes);
+                let reference = (offset, len as u32);
+                new_chunks.insert(digest, reference);
+                reference
+            };
+
+            chunks.push(ChunkRef { digest, offset, length });
+        }
+
+        entries.push(FileEntry {
+            path: rel_path.to_string_lossy().replace('\\', "/"),
+            chunks,
+        });
+    }
+
+    let mut blob = Vec::new();
+    if let Some(existing_blob) = existing_blob {
+        blob.extend_from_slice(existing_blob);
+    }
+    blob.extend_from_slice(&appended);
+
+    let mut all_chunks: Vec<([u8; 32], u64, u32)> = Vec::new();
+    if let Some(existing_data) = existing.as_deref() {
+        for_each_chunk_entry(existing_data, chunk_catalog_offset, chunk_catalog_count, |digest, offset, length| {
+            all_chunks.push((digest, offset, length));
+        });
+    }
+    for (digest, (offset, length)) in new_chunks.iter() {
+        all_chunks.push((*digest, *offset, *length));
+    }
+    all_chunks.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let out = serialize(&blob, &entries, &all_chunks);
+
+    if let Err(e) = fs::write(out_path, &out) {
+        eprintln!("Error writing {out_path}: {e}");
+        process::exit(1);
+    }
+
+    let mode = if existing.is_some() { "Incrementally packed" } else { "Packed" };
+    println!(
+        "{mode} {} files ({} chunks, {} unique total, {} reused) into {out_path}",
+        entries.len(),
+        total_chunks,
+        all_chunks.len(),
+        reused_chunks
+    );
+}
+
+pub fn unpack(pack_path: &str, out_dir: &str) {
+    let data = match fs::read(pack_path) {
+        Ok(d) => d,
+        Err(e) => {
+            eprintln!("Error reading {pack_path}: {e}");
+            process::exit(1);
+        }
+    };
+
+    let footer = read_footer(&data);
+    let entries = read_file_index(&data, footer.file_index_offset as usize, footer.file_count);
+
+    let out_dir = Path::new(out_dir);
+    for entry in &entries {
+        write_extracted(&data, entry, out_dir);
+    }
+
+    println!("Unpacked {} files into {}", entries.len(), out_dir.display());
+}
+
+/// Extracts a single asset from the archive by path, using the sorted path
+/// catalog to binary-search its file entry rather than scanning the whole
+/// index.
+pub fn get(pack_path: &str, asset_path: &str, out_path: &str) {
+    let data = match fs::read(pack_path) {
+        Ok(d) => d,
+        Err(e) => {
+            eprintln!("Error reading {pack_path}: {e}");
+            process::exit(1);
+        }
+    };
+
+    let footer = read_footer(&data);
+    let target_hash = fnv1a64(asset_path);
+
+    let entry_offset = binary_search_path_catalog(
+        &data,
+        footer.path_catalog_offset,
+        footer.file_count,
+        target_hash,
+    );
+
+    let mut offset = match entry_offset {
+        Some(o) => o as usize,
+        None => {
+            eprintln!("Error: {asset_path} not found in {pack_path}");
+            process::exit(1);
+        }
+    };
+
+    let entry = read_file_entry(&data, &mut offset);
+    if entry.path != asset_path {
+        eprintln!("Error: {asset_path} not found in {pack_path}");
+        process::exit(1);
+    }
+
+    write_to_file(&data, &entry, Path::new(out_path));
+    println!("Extracted {} to {out_path}", entry.path);
+}
+
+/// Reconstructs `entry`'s contents from `data` and writes them straight to
+/// `dest_path`, ignoring the archive's stored path entirely. Used by `get`,
+/// which lets the caller pick an arbitrary output file name; contrast with
+/// `write_extracted`, which recreates the archive's relative tree under an
+/// output directory for bulk `unpack`.
+fn write_to_file(data: &[u8], entry: &FileEntry, dest_path: &Path) {
+    let mut contents = Vec::new();
+    for chunk in &entry.chunks {
+        let start = 4 + chunk.offset as usize;
+        let end = start + chunk.length as usize;
+        contents.extend_from_slice(&data[start..end]);
+    }
+
+    if let Some(parent) = dest_path.parent() {
+        if !parent.as_os_str().is_empty() {
+            if let Err(e) = fs::create_dir_all(parent) {
+                eprintln!("Error creating {}: {e}", parent.display());
+                process::exit(1);
+            }
+        }
+    }
+    if let Err(e) = fs::write(dest_path, &contents) {
+        eprintln!("Error writing {}: {e}", dest_path.display());
+        process::exit(1);
+    }
+}
+
+fn write_extracted(data: &[u8], entry: &FileEntry, out_dir: &Path) {
+    let mut contents = Vec::new();
+    for chunk in &entry.chunks {
+        let start = 4 + chunk.offset as usize;
+        let end = start + chunk.length as usize;
+        contents.extend_from_slice(&data[start..end]);
+    }
+
+    let dest = match safe_join(out_dir, &entry.path) {
+        Some(dest) => dest,
+        None => {
+            eprintln!("Error: archive entry escapes output directory: {}", entry.path);
+            process::exit(1);
+        }
+    };
+    if let Some(parent) = dest.parent() {
+        if let Err(e) = fs::create_dir_all(parent) {
+            eprintln!("Error creating {}: {e}", parent.display());
+            process::exit(1);
+        }
+    }
+    if let Err(e) = fs::write(&dest, &contents) {
+        eprintln!("Error writing {}: {e}", dest.display());
+        process::exit(1);
+    }
+}
+
+/// Joins `entry_path` (a path read verbatim from an `.mpak` archive, which
+/// is untrusted input) onto `out_dir`, rejecting any entry that would climb
+/// out of it via `..` or an absolute/prefix component (zip-slip).
+fn safe_join(out_dir: &Path, entry_path: &str) -> Option<PathBuf> {
+    use std::path::Component;
+
+    let mut dest = out_dir.to_path_buf();
+    for component in Path::new(entry_path).components() {
+        match component {
+            Component::Normal(part) => dest.push(part),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    Some(dest)
+}
+
+fn collect_files(dir: &Path, base: &Path, out: &mut Vec<PathBuf>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(e) => e,
+        Err(_) => return,
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(&path, base, out);
+        } else if let Ok(rel) = path.strip_prefix(base) {
+            out.push(rel.to_path_buf());
+        }
+    }
+}
+
+/// Splits `data` into content-defined chunks using a buzhash rolling hash
+/// over a `WINDOW_SIZE`-byte window. A boundary is cut when the low
+/// `mask_bits` bits of the hash are zero, clamped to `[min_size, max_size]`.
+fn split_chunks(data: &[u8], min_size: usize, max_size: usize, mask_bits: u32) -> Vec<(usize, usize)> {
+    if data.is_empty() {
+        return Vec::new();
+    }
+
+    let mask: u32 = (1u32 << mask_bits) - 1;
+    let table = buzhash_table();
+    let mut chunks = Vec::new();
+    let mut chunk_start = 0usize;
+
+    while chunk_start < data.len() {
+        let remaining = data.len() - chunk_start;
+        if remaining <= min_size {
+            chunks.push((chunk_start, remaining));
+            break;
+        }
+
+        let mut hash: u32 = 0;
+        let mut window = [0u8; WINDOW_SIZE];
+        let mut window_pos = 0usize;
+        let mut len = 0usize;
+        let max_len = remaining.min(max_size);
+
+        loop {
+            let byte = data[chunk_start + len];
+            if len < WINDOW_SIZE {
+                hash = hash.rotate_left(1) ^ table[byte as usize];
+            } else {
+                let out_byte = window[window_pos];
+                hash = hash.rotate_left(1)
+                    ^ table[byte as usize]
+                    ^ table[out_byte as usize].rotate_left(WINDOW_SIZE as u32 % 32);
+            }
+            window[window_pos] = byte;
+            window_pos = (window_pos + 1) % WINDOW_SIZE;
+            len += 1;
+
+            if len >= min_size && (hash & mask) == 0 {
+                break;
+            }
+            if len >= max_len {
+                break;
+            }
+        }
+
+        chunks.push((chunk_start, len));
+        chunk_start += len;
+    }
+
+    chunks
+}
+
+/// A table of pseudo-random 32-bit constants, one per byte value, used by
+/// the buzhash rolling hash. Generated deterministically with splitmix64 so
+/// every invocation of the packer agrees on chunk boundaries without
+/// shipping a precomputed table.
+fn buzhash_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut seed: u64 = 0x9E3779B97F4A7C15;
+    for slot in table.iter_mut() {
+        seed = seed.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = seed;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^= z >> 31;
+        *slot = (z >> 32) as u32;
+    }
+    table
+}
+
+/// FNV-1a 64-bit hash, used to key the sorted path catalog.
+fn fnv1a64(s: &str) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+    let mut hash = OFFSET_BASIS;
+    for byte in s.as_bytes() {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(PRIME);
+    }
+    hash
+}
+
+/// Serializes the blob, file index, chunk catalog and path catalog into the
+/// final `.mpak` byte layout:
+/// `[magic][blob][file index][chunk catalog][path catalog][footer]`.
+fn serialize(blob: &[u8], entries: &[FileEntry], chunks: &[([u8; 32], u64, u32)]) -> Vec<u8> {
+    let mut out = Vec::new();
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(blob);
+
+    let file_index_offset = out.len() as u64;
+    write_u32(&mut out, entries.len() as u32);
+    let mut path_catalog: Vec<(u64, u64)> = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let entry_offset = out.len() as u64;
+        write_string(&mut out, &entry.path);
+        write_u32(&mut out, entry.chunks.len() as u32);
+        for chunk in &entry.chunks {
+            write_u64(&mut out, chunk.offset);
+            write_u32(&mut out, chunk.length);
+            out.extend_from_slice(&chunk.digest);
+        }
+        path_catalog.push((fnv1a64(&entry.path), entry_offset));
+    }
+
+    let chunk_catalog_offset = out.len() as u64;
+    write_u32(&mut out, chunks.len() as u32);
+    for (digest, offset, length) in chunks {
+        out.extend_from_slice(digest);
+        write_u64(&mut out, *offset);
+        write_u32(&mut out, *length);
+    }
+
+    path_catalog.sort_by_key(|&(hash, _)| hash);
+    let path_catalog_offset = out.len() as u64;
+    write_u32(&mut out, path_catalog.len() as u32);
+    for (hash, entry_offset) in &path_catalog {
+        write_u64(&mut out, *hash);
+        write_u64(&mut out, *entry_offset);
+    }
+
+    write_u64(&mut out, file_index_offset);
+    write_u64(&mut out, chunk_catalog_offset);
+    write_u64(&mut out, path_catalog_offset);
+    write_u32(&mut out, entries.len() as u32);
+    out.extend_from_slice(MAGIC);
+
+    out
+}
+
+struct Footer {
+    file_index_offset: u64,
+    chunk_catalog_offset: u64,
+    path_catalog_offset: u64,
+    file_count: u32,
+    chunk_catalog_count: u32,
+}
+
+fn read_footer(data: &[u8]) -> Footer {
+    if data.len() < 4 + FOOTER_SIZE {
+        eprintln!("Error: file too small to be a valid .mpak archive");
+        process::exit(1);
+    }
+
+    if &data[data.len() - 4..] != MAGIC {
+        eprintln!("Error: not a valid .mpak archive (bad footer magic)");
+        process::exit(1);
+    }
+
+    let mut offset = data.len() - FOOTER_SIZE;
+    let file_index_offset = read_u64(data, &mut offset);
+    let chunk_catalog_offset = read_u64(data, &mut offset);
+    let path_catalog_offset = read_u64(data, &mut offset);
+    let file_count = read_u32(data, &mut offset);
+
+    let chunk_catalog_count = u32::from_le_bytes(
+        data[chunk_catalog_offset as usize..chunk_catalog_offset as usize + 4]
+            .try_into()
+            .unwrap(),
+    );
+
+    Footer {
+        file_index_offset,
+        chunk_catalog_offset,
+        path_catalog_offset,
+        file_count,
+        chunk_catalog_count,
+    }
+}
+
+fn read_file_index(data: &[u8], mut offset: usize, file_count: u32) -> Vec<FileEntry> {
+    let stored_file_count = read_u32(data, &mut offset);
+    if stored_file_count != file_count {
+        eprintln!("Error: corrupt .mpak index (file count mismatch)");
+        process::exit(1);
+    }
+
+    let mut entries = Vec::with_capacity(file_count as usize);
+    for _ in 0..file_count {
+        entries.push(read_file_entry(data, &mut offset));
+    }
+    entries
+}
+
+fn read_file_entry(data: &[u8], offset: &mut usize) -> FileEntry {
+    let path = read_string(data, offset);
+    let chunk_count = read_u32(data, offset);
+    let mut chunks = Vec::with_capacity(chunk_count as usize);
+    for _ in 0..chunk_count {
+        let chunk_offset = read_u64(data, offset);
+        let length = read_u32(data, offset);
+        let mut digest = [0u8; 32];
+        digest.copy_from_slice(&data[*offset..*offset + 32]);
+        *offset += 32;
+        chunks.push(ChunkRef { digest, offset: chunk_offset, length });
+    }
+    FileEntry { path, chunks }
+}
+
+/// Binary-searches the on-disk, digest-sorted chunk catalog for `digest`,
+/// returning its `(offset, length)` in the blob region if present. Used to
+/// decide, during an incremental repack, whether a chunk can be reused
+/// instead of appended again.
+fn find_chunk(data: &[u8], catalog_offset: u64, count: u32, digest: &[u8; 32]) -> Option<(u64, u32)> {
+    let base = catalog_offset as usize + 4;
+    let mut lo = 0i64;
+    let mut hi = count as i64 - 1;
+
+    while lo <= hi {
+        let mid = (lo + hi) / 2;
+        let entry_offset = base + mid as usize * CHUNK_CATALOG_ENTRY_SIZE;
+        let entry_digest = &data[entry_offset..entry_offset + 32];
+
+        match entry_digest.cmp(digest.as_slice()) {
+            std::cmp::Ordering::Equal => {
+                let mut off = entry_offset + 32;
+                let offset = read_u64(data, &mut off);
+                let length = read_u32(data, &mut off);
+                return Some((offset, length));
+            }
+            std::cmp::Ordering::Less => lo = mid + 1,
+            std::cmp::Ordering::Greater => hi = mid - 1,
+        }
+    }
+
+    None
+}
+
+fn for_each_chunk_entry(data: &[u8], catalog_offset: u64, count: u32, mut f: impl FnMut([u8; 32], u64, u32)) {
+    let mut offset = catalog_offset as usize + 4;
+    for _ in 0..count {
+        let mut digest = [0u8; 32];
+        digest.copy_from_slice(&data[offset..offset + 32]);
+        offset += 32;
+        let chunk_offset = read_u64(data, &mut offset);
+        let length = read_u32(data, &mut offset);
+        f(digest, chunk_offset, length);
+    }
+}
+
+/// Binary-searches the on-disk, hash-sorted path catalog for `target_hash`,
+/// returning the absolute byte offset of the matching file entry.
+fn binary_search_path_catalog(data: &[u8], catalog_offset: u64, count: u32, target_hash: u64) -> Option<u64> {
+    let base = catalog_offset as usize + 4;
+    let mut lo = 0i64;
+    let mut hi = count as i64 - 1;
+
+    while lo <= hi {
+        let mid = (lo + hi) / 2;
+        let entry_offset = base + mid as usize * PATH_CATALOG_ENTRY_SIZE;
+        let mut off = entry_offset;
+        let hash = read_u64(data, &mut off);
+
+        match hash.cmp(&target_hash) {
+            std::cmp::Ordering::Equal => return Some(read_u64(data, &mut off)),
+            std::cmp::Ordering::Less => lo = mid + 1,
+            std::cmp::Ordering::Greater => hi = mid - 1,
+        }
+    }
+
+    None
+}
+
+fn write_u32(buf: &mut Vec<u8>, v: u32) {
+    buf.extend_from_slice(&v.to_le_bytes());
+}
+
+fn write_u64(buf: &mut Vec<u8>, v: u64) {
+    buf.extend_from_slice(&v.to_le_bytes());
+}
+
+fn write_string(buf: &mut Vec<u8>, s: &str) {
+    write_u32(buf, s.len() as u32);
+    buf.extend_from_slice(s.as_bytes());
+}
+
+fn read_u32(data: &[u8], offset: &mut usize) -> u32 {
+    let v = u32::from_le_bytes(data[*offset..*offset + 4].try_into().unwrap());
+    *offset += 4;
+    v
+}
+
+fn read_u64(data: &[u8], offset: &mut usize) -> u64 {
+    let v = u64::from_le_bytes(data[*offset..*offset + 8].try_into().unwrap());
+    *offset += 8;
+    v
+}
+
+fn read_string(data: &[u8], offset: &mut usize) -> String {
+    let len = read_u32(data, offset) as usize;
+    let s = String::from_utf8_lossy(&data[*offset..*offset + len]).into_owned();
+    *offset += len;
+    s
+}
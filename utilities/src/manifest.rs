@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::process;
+
+use serde::Deserialize;
+
+/// Name of the project manifest file, looked up in the current directory.
+pub const MANIFEST_FILE: &str = "mirgo.toml";
+
+/// Project-wide configuration read from `mirgo.toml`. Every subcommand that
+/// used to hardcode a path (`internal/components/scripts`, `./cmd/test3d`,
+/// ...) now takes its value from here instead, so the tool works for any
+/// project layout, not just the default one.
+#[derive(Debug, Deserialize)]
+pub struct Manifest {
+    /// Go module name (the `module` line in `go.mod`), used as the import
+    /// prefix when generating Go source such as `vendor_register.go`.
+    #[serde(default = "default_module")]
+    pub module: String,
+    #[serde(default = "default_scripts_dir")]
+    pub scripts_dir: String,
+    #[serde(default = "default_assets_dir")]
+    pub assets_dir: String,
+    #[serde(default = "default_entrypoint")]
+    pub entrypoint: String,
+    #[serde(default)]
+    pub profiles: HashMap<String, Profile>,
+    #[serde(default)]
+    pub dependencies: HashMap<String, String>,
+}
+
+/// One named build profile, e.g. `[profiles.release]`. Consumed by the
+/// `build` subcommand to drive per-target packaging metadata.
+#[derive(Debug, Deserialize, Clone)]
+pub struct Profile {
+    #[serde(default)]
+    pub targets: Vec<String>,
+    #[serde(default)]
+    pub identifier: Option<String>,
+    #[serde(default)]
+    pub icon: Option<String>,
+}
+
+fn default_module() -> String {
+    "test3d".to_string()
+}
+
+fn default_scripts_dir() -> String {
+    "internal/components/scripts".to_string()
+}
+
+fn default_assets_dir() -> String {
+    "assets".to_string()
+}
+
+fn default_entrypoint() -> String {
+    "./cmd/test3d".to_string()
+}
+
+impl Default for Manifest {
+    fn default() -> Self {
+        Manifest {
+            module: default_module(),
+            scripts_dir: default_scripts_dir(),
+            assets_dir: default_assets_dir(),
+            entrypoint: default_entrypoint(),
+            profiles: HashMap::new(),
+            dependencies: HashMap::new(),
+        }
+    }
+}
+
+/// Loads `mirgo.toml` from the current directory. Projects that don't have
+/// one yet fall back to `Manifest::default()`, which matches the paths this
+/// tool used to hardcode, so existing projects keep working unchanged.
+pub fn load() -> Manifest {
+    let path = Path::new(MANIFEST_FILE);
+    if !path.exists() {
+        return Manifest::default();
+    }
+
+    let content = match fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Error reading {MANIFEST_FILE}: {e}");
+            process::exit(1);
+        }
+    };
+
+    match toml::from_str(&content) {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            eprintln!("Error parsing {MANIFEST_FILE}: {e}");
+            process::exit(1);
+        }
+    }
+}